@@ -0,0 +1,399 @@
+use std::sync::Arc;
+
+use axum::body::{Body, Bytes};
+use axum::extract::{Path, Query, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Form, Json, Router};
+use log::{error, info, warn};
+use serde::{Deserialize, Serialize};
+use tokio_util::io::ReaderStream;
+
+use crate::auth::{self, TokenVerifier};
+use crate::media::{self, MediaStore};
+use crate::micropub;
+use crate::models::{Mention, Post, PostFilter, PostsPage};
+use crate::storage::{self, Storage};
+use crate::webmention;
+
+pub struct Microblog {
+    pub(crate) storage: Arc<dyn Storage>,
+    media_store: Arc<dyn MediaStore>,
+    token_verifier: Arc<dyn TokenVerifier>,
+    base_url: String,
+    http_client: reqwest::Client,
+}
+
+impl Microblog {
+    pub async fn new(
+        database_url: &str,
+        base_url: &str,
+        media_dir: &str,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let storage = storage::from_database_url(database_url).await?;
+        Ok(Microblog {
+            token_verifier: Arc::new(auth::LocalTokenVerifier::new(Arc::clone(&storage))),
+            storage,
+            media_store: Arc::new(media::FsMediaStore::new(media_dir)),
+            base_url: base_url.to_string(),
+            http_client: crate::tls::http_client(),
+        })
+    }
+
+    async fn create_post(&self, title: String, content: String) -> Result<Post, String> {
+        let post = self.storage.create_post(title, content).await?;
+        self.queue_outbound_webmentions(&post).await;
+        Ok(post)
+    }
+
+    async fn get_posts(&self, filter: PostFilter) -> Result<PostsPage, String> {
+        self.storage.get_posts(filter).await
+    }
+
+    async fn get_post(&self, id: uuid::Uuid) -> Result<Option<Post>, String> {
+        self.storage.get_post(id).await
+    }
+
+    /// Scans a freshly created post for outbound links and queues each one
+    /// for the background worker. Endpoint discovery happens there, not
+    /// here, so a slow or unresponsive link can't hang this request.
+    async fn queue_outbound_webmentions(&self, post: &Post) {
+        let source = format!("{}/posts/{}", self.base_url, post.id);
+        for target in webmention::extract_links(&post.content) {
+            if let Err(e) = self.storage.enqueue_webmention(source.clone(), target).await {
+                error!("Failed to enqueue webmention: {}", e);
+            }
+        }
+    }
+
+    /// Handles an inbound webmention: fetches `source`, confirms it really
+    /// links to `target`, and only then persists the mention.
+    async fn receive_webmention(&self, source: String, target: String) -> Result<(), String> {
+        if !self.owns(&target) {
+            return Err("Target does not belong to this site".to_string());
+        }
+
+        let body = self
+            .http_client
+            .get(&source)
+            .send()
+            .await
+            .map_err(|e| {
+                warn!("Failed to fetch webmention source {}: {:?}", source, e);
+                "Failed to fetch source"
+            })?
+            .text()
+            .await
+            .map_err(|e| {
+                warn!("Failed to read webmention source {}: {:?}", source, e);
+                "Failed to read source"
+            })?;
+
+        if !body.contains(&target) {
+            return Err("Source does not link to target".to_string());
+        }
+
+        self.storage
+            .store_mention(Mention {
+                id: uuid::Uuid::new_v4(),
+                source,
+                target,
+            })
+            .await
+    }
+
+    /// Whether `target` belongs to this site, compared by scheme, host and
+    /// port rather than by string prefix (a bare prefix check would let
+    /// `http://example.com.evil.com` pass for `base_url = "http://example.com"`).
+    fn owns(&self, target: &str) -> bool {
+        let (Ok(target), Ok(base)) = (url::Url::parse(target), url::Url::parse(&self.base_url))
+        else {
+            return false;
+        };
+        target.scheme() == base.scheme()
+            && target.host_str() == base.host_str()
+            && target.port_or_known_default() == base.port_or_known_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn blog(base_url: &str) -> Microblog {
+        Microblog::new("memory://", base_url, "media_storage")
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn owns_accepts_the_site_itself() {
+        let blog = blog("http://example.com").await;
+        assert!(blog.owns("http://example.com/posts/1"));
+    }
+
+    #[tokio::test]
+    async fn owns_rejects_a_spoofed_subdomain_suffix() {
+        // 1d154b2 fixed a prefix-matching bug where this URL passed because
+        // it merely starts with the base_url string; guard against it
+        // regressing back.
+        let blog = blog("http://example.com").await;
+        assert!(!blog.owns("http://example.com.evil.com/posts/1"));
+    }
+
+    #[tokio::test]
+    async fn owns_rejects_mismatched_scheme_and_port() {
+        let blog = blog("http://example.com").await;
+        assert!(!blog.owns("https://example.com/posts/1"));
+        assert!(!blog.owns("http://example.com:8080/posts/1"));
+    }
+}
+
+/// Collects every failure mode a handler can hit into one `IntoResponse`
+/// type, so handlers can return `Result<_, AppError>` instead of each
+/// building its own error `Response`.
+enum AppError {
+    BadRequest(String),
+    Unauthorized(String),
+    Forbidden(String),
+    NotFound(String),
+    Internal(String),
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        match self {
+            AppError::BadRequest(message) => (StatusCode::BAD_REQUEST, message).into_response(),
+            AppError::Unauthorized(message) => (
+                StatusCode::UNAUTHORIZED,
+                [(header::WWW_AUTHENTICATE, "Bearer")],
+                message,
+            )
+                .into_response(),
+            AppError::Forbidden(message) => (
+                StatusCode::FORBIDDEN,
+                [(
+                    header::WWW_AUTHENTICATE,
+                    "Bearer error=\"insufficient_scope\"",
+                )],
+                message,
+            )
+                .into_response(),
+            AppError::NotFound(message) => (StatusCode::NOT_FOUND, message).into_response(),
+            AppError::Internal(message) => {
+                error!("Internal error: {}", message);
+                (StatusCode::INTERNAL_SERVER_ERROR, message).into_response()
+            }
+        }
+    }
+}
+
+/// Checks the request's `Authorization` header for a bearer token carrying
+/// `required_scope`, distinguishing a missing token (`401`), an invalid one
+/// (`401`), and one lacking the scope (`403`).
+async fn authorize(
+    headers: &HeaderMap,
+    blog: &Microblog,
+    required_scope: &str,
+) -> Result<auth::Identity, AppError> {
+    let header = headers
+        .get(header::AUTHORIZATION)
+        .ok_or_else(|| AppError::Unauthorized("Missing bearer token".to_string()))?;
+    let header = header
+        .to_str()
+        .map_err(|_| AppError::Unauthorized("Malformed Authorization header".to_string()))?;
+    let token = header
+        .strip_prefix("Bearer ")
+        .ok_or_else(|| AppError::Unauthorized("Malformed Authorization header".to_string()))?;
+
+    match blog.token_verifier.verify(token).await {
+        Ok(identity) if identity.has_scope(required_scope) => Ok(identity),
+        Ok(_) => Err(AppError::Forbidden("Token lacks required scope".to_string())),
+        Err(_) => Err(AppError::Unauthorized("Invalid bearer token".to_string())),
+    }
+}
+
+#[derive(Deserialize)]
+struct CreatePostRequest {
+    title: String,
+    content: String,
+}
+
+async fn create_post(
+    State(blog): State<Arc<Microblog>>,
+    headers: HeaderMap,
+    Json(payload): Json<CreatePostRequest>,
+) -> Result<Json<Post>, AppError> {
+    info!("Create post");
+    authorize(&headers, &blog, "create").await?;
+
+    if payload.title.is_empty() || payload.content.is_empty() {
+        return Err(AppError::BadRequest(
+            "Title and content cannot be empty".to_string(),
+        ));
+    }
+
+    blog.create_post(payload.title, payload.content)
+        .await
+        .map(Json)
+        .map_err(AppError::Internal)
+}
+
+#[derive(Deserialize)]
+struct PostsQuery {
+    limit: Option<u32>,
+    before: Option<i64>,
+    q: Option<String>,
+}
+
+async fn get_posts(
+    State(blog): State<Arc<Microblog>>,
+    Query(params): Query<PostsQuery>,
+) -> Result<Json<PostsPage>, AppError> {
+    let filter = PostFilter {
+        limit: params
+            .limit
+            .unwrap_or(storage::DEFAULT_POST_LIMIT)
+            .clamp(1, storage::MAX_POST_LIMIT),
+        before: params.before,
+        query: params.q.filter(|q| !q.is_empty()),
+    };
+
+    blog.get_posts(filter)
+        .await
+        .map(Json)
+        .map_err(AppError::Internal)
+}
+
+async fn get_post(
+    State(blog): State<Arc<Microblog>>,
+    Path(id): Path<uuid::Uuid>,
+) -> Result<Json<Post>, AppError> {
+    let post = blog.get_post(id).await.map_err(AppError::Internal)?;
+    post.map(Json)
+        .ok_or_else(|| AppError::NotFound("Post not found".to_string()))
+}
+
+#[derive(Deserialize)]
+struct WebmentionForm {
+    source: String,
+    target: String,
+}
+
+async fn webmention_handler(
+    State(blog): State<Arc<Microblog>>,
+    Form(form): Form<WebmentionForm>,
+) -> Result<StatusCode, AppError> {
+    info!("Receive webmention");
+    blog.receive_webmention(form.source, form.target)
+        .await
+        .map(|_| StatusCode::ACCEPTED)
+        .map_err(AppError::BadRequest)
+}
+
+#[derive(Serialize)]
+struct MediaCreated {
+    url: String,
+}
+
+async fn create_media(
+    State(blog): State<Arc<Microblog>>,
+    headers: HeaderMap,
+    body: Body,
+) -> Result<Response, AppError> {
+    info!("Create media");
+    authorize(&headers, &blog, "create").await?;
+
+    let content_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("application/octet-stream")
+        .to_string();
+
+    let id = blog
+        .media_store
+        .put(content_type, body)
+        .await
+        .map_err(AppError::Internal)?;
+    let location = format!("/media/{}", id);
+
+    Ok((
+        StatusCode::CREATED,
+        [(header::LOCATION, location.clone())],
+        Json(MediaCreated { url: location }),
+    )
+        .into_response())
+}
+
+async fn get_media(
+    State(blog): State<Arc<Microblog>>,
+    Path(id): Path<String>,
+) -> Result<Response, AppError> {
+    let stored = blog
+        .media_store
+        .get(&id)
+        .await
+        .map_err(AppError::Internal)?
+        .ok_or_else(|| AppError::NotFound("Media not found".to_string()))?;
+
+    let file = tokio::fs::File::open(&stored.path).await.map_err(|e| {
+        error!("Failed to open media file: {:?}", e);
+        AppError::Internal("Failed to read media".to_string())
+    })?;
+
+    let body = Body::from_stream(ReaderStream::new(file));
+
+    Ok((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, stored.content_type),
+            (header::CONTENT_LENGTH, stored.content_length.to_string()),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+async fn micropub_handler(
+    State(blog): State<Arc<Microblog>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Response, AppError> {
+    info!("Micropub post");
+    authorize(&headers, &blog, "create").await?;
+
+    let content_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("")
+        .split(';')
+        .next()
+        .unwrap_or("");
+
+    let entry = if content_type == "application/json" {
+        micropub::parse_json(&body)
+    } else {
+        micropub::parse_form(&body)
+    }
+    .map_err(AppError::BadRequest)?;
+
+    let post = blog
+        .create_post(entry.title, entry.content)
+        .await
+        .map_err(AppError::Internal)?;
+    let location = format!("{}/posts/{}", blog.base_url, post.id);
+
+    Ok((StatusCode::CREATED, [(header::LOCATION, location)]).into_response())
+}
+
+pub fn build_router(blog: Arc<Microblog>) -> Router {
+    Router::new()
+        .route("/posts", get(get_posts).post(create_post))
+        .route("/posts/{id}", get(get_post))
+        .route("/webmention", post(webmention_handler))
+        .route("/media", post(create_media))
+        .route("/media/{id}", get(get_media))
+        .route("/micropub", post(micropub_handler))
+        .with_state(blog)
+}
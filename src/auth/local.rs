@@ -0,0 +1,70 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::auth::{AuthError, Identity, TokenVerifier};
+use crate::storage::Storage;
+
+/// Verifies bearer tokens against the `tokens` table in the configured
+/// storage backend.
+pub struct LocalTokenVerifier {
+    storage: Arc<dyn Storage>,
+}
+
+impl LocalTokenVerifier {
+    pub fn new(storage: Arc<dyn Storage>) -> Self {
+        LocalTokenVerifier { storage }
+    }
+}
+
+#[async_trait]
+impl TokenVerifier for LocalTokenVerifier {
+    async fn verify(&self, token: &str) -> Result<Identity, AuthError> {
+        match self.storage.lookup_token(token).await {
+            Ok(Some(record)) => Ok(Identity {
+                me: record.me,
+                scopes: record.scopes,
+            }),
+            _ => Err(AuthError::Invalid),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TokenRecord;
+    use crate::storage::MemoryStore;
+
+    #[tokio::test]
+    async fn verifies_a_known_token_and_carries_its_scopes() {
+        let storage: Arc<dyn Storage> = Arc::new(MemoryStore::new());
+        storage
+            .insert_token(
+                "tok".to_string(),
+                TokenRecord {
+                    me: "https://example.com/".to_string(),
+                    scopes: vec!["create".to_string()],
+                },
+            )
+            .await
+            .unwrap();
+        let verifier = LocalTokenVerifier::new(storage);
+
+        let identity = verifier.verify("tok").await.expect("token should verify");
+        assert_eq!(identity.me, "https://example.com/");
+        assert!(identity.has_scope("create"));
+        assert!(!identity.has_scope("delete"));
+    }
+
+    #[tokio::test]
+    async fn rejects_an_unknown_token() {
+        let storage: Arc<dyn Storage> = Arc::new(MemoryStore::new());
+        let verifier = LocalTokenVerifier::new(storage);
+
+        assert!(matches!(
+            verifier.verify("missing").await,
+            Err(AuthError::Invalid)
+        ));
+    }
+}
@@ -0,0 +1,51 @@
+mod local;
+mod remote;
+
+pub use local::LocalTokenVerifier;
+pub use remote::RemoteTokenVerifier;
+
+use async_trait::async_trait;
+
+/// The authenticated caller behind a bearer token: who they are (`me`, an
+/// IndieAuth profile URL) and what they're allowed to do.
+#[derive(Clone, Debug)]
+pub struct Identity {
+    pub me: String,
+    pub scopes: Vec<String>,
+}
+
+impl Identity {
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scopes.iter().any(|s| s == scope)
+    }
+}
+
+/// Why a bearer token failed to verify.
+#[derive(Debug)]
+pub enum AuthError {
+    Invalid,
+}
+
+/// Turns a bearer token into an `Identity`, the way Kittybox's `tokenauth`
+/// abstracts over locally-issued and remotely-verified tokens alike.
+#[async_trait]
+pub trait TokenVerifier: Send + Sync {
+    async fn verify(&self, token: &str) -> Result<Identity, AuthError>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn has_scope_matches_only_granted_scopes() {
+        let identity = Identity {
+            me: "https://example.com/".to_string(),
+            scopes: vec!["create".to_string(), "update".to_string()],
+        };
+
+        assert!(identity.has_scope("create"));
+        assert!(identity.has_scope("update"));
+        assert!(!identity.has_scope("delete"));
+    }
+}
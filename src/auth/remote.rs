@@ -0,0 +1,63 @@
+use async_trait::async_trait;
+use log::warn;
+use serde::Deserialize;
+
+use crate::auth::{AuthError, Identity, TokenVerifier};
+
+#[derive(Deserialize)]
+struct TokenEndpointResponse {
+    me: String,
+    #[serde(default)]
+    scope: String,
+}
+
+/// Verifies bearer tokens by asking a configured IndieAuth-style token
+/// endpoint, for tokens this server didn't mint itself.
+pub struct RemoteTokenVerifier {
+    endpoint: String,
+    client: reqwest::Client,
+}
+
+impl RemoteTokenVerifier {
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        RemoteTokenVerifier {
+            endpoint: endpoint.into(),
+            client: crate::tls::http_client(),
+        }
+    }
+}
+
+#[async_trait]
+impl TokenVerifier for RemoteTokenVerifier {
+    async fn verify(&self, token: &str) -> Result<Identity, AuthError> {
+        let response = self
+            .client
+            .get(&self.endpoint)
+            .header(reqwest::header::AUTHORIZATION, format!("Bearer {}", token))
+            .header(reqwest::header::ACCEPT, "application/json")
+            .send()
+            .await
+            .map_err(|e| {
+                warn!("Token verification request failed: {:?}", e);
+                AuthError::Invalid
+            })?;
+
+        if !response.status().is_success() {
+            return Err(AuthError::Invalid);
+        }
+
+        let parsed: TokenEndpointResponse = response.json().await.map_err(|e| {
+            warn!("Failed to parse token verification response: {:?}", e);
+            AuthError::Invalid
+        })?;
+
+        Ok(Identity {
+            me: parsed.me,
+            scopes: parsed
+                .scope
+                .split_whitespace()
+                .map(str::to_string)
+                .collect(),
+        })
+    }
+}
@@ -0,0 +1,187 @@
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use axum::body::Body;
+use http_body_util::BodyExt;
+use log::error;
+use sha2::{Digest, Sha256};
+use tokio::fs::File;
+use tokio::io::AsyncWriteExt;
+use uuid::Uuid;
+
+use crate::media::{MediaStore, StoredMedia};
+
+/// Filesystem-backed `MediaStore`.
+///
+/// Blobs are written to a temp file under `base_dir` while being hashed, then
+/// atomically renamed to their content hash so two uploads of the same bytes
+/// dedup onto one file. Content type is kept in a `.meta` sidecar next to the
+/// blob, since the hash alone doesn't carry it.
+pub struct FsMediaStore {
+    base_dir: PathBuf,
+}
+
+impl FsMediaStore {
+    pub fn new(base_dir: impl Into<PathBuf>) -> Self {
+        FsMediaStore {
+            base_dir: base_dir.into(),
+        }
+    }
+
+    fn blob_path(&self, id: &str) -> PathBuf {
+        self.base_dir.join(id)
+    }
+
+    fn meta_path(&self, id: &str) -> PathBuf {
+        self.base_dir.join(format!("{}.meta", id))
+    }
+}
+
+/// `put()` only ever hands back lowercase hex SHA-256 digests, so anything
+/// else reaching `get()` is either a typo or a path-traversal attempt
+/// (e.g. a percent-decoded `../../etc/passwd`) and must never be joined
+/// onto `base_dir`.
+fn is_valid_id(id: &str) -> bool {
+    id.len() == 64 && id.bytes().all(|b| b.is_ascii_hexdigit() && !b.is_ascii_uppercase())
+}
+
+#[async_trait]
+impl MediaStore for FsMediaStore {
+    async fn put(&self, content_type: String, mut body: Body) -> Result<String, String> {
+        tokio::fs::create_dir_all(&self.base_dir)
+            .await
+            .map_err(|e| {
+                error!("Failed to create media directory: {:?}", e);
+                "Failed to store media"
+            })?;
+
+        let temp_path = self.base_dir.join(format!(".upload-{}", Uuid::new_v4()));
+        let mut temp_file = File::create(&temp_path).await.map_err(|e| {
+            error!("Failed to create temp file: {:?}", e);
+            "Failed to store media"
+        })?;
+
+        let mut hasher = Sha256::new();
+        while let Some(frame) = body.frame().await {
+            let frame = frame.map_err(|e| {
+                error!("Failed to read upload body: {:?}", e);
+                "Failed to read upload body"
+            })?;
+            if let Some(chunk) = frame.data_ref() {
+                hasher.update(chunk);
+                temp_file.write_all(chunk).await.map_err(|e| {
+                    error!("Failed to write media chunk: {:?}", e);
+                    "Failed to store media"
+                })?;
+            }
+        }
+        temp_file.flush().await.map_err(|e| {
+            error!("Failed to flush media upload: {:?}", e);
+            "Failed to store media"
+        })?;
+        drop(temp_file);
+
+        let id = format!("{:x}", hasher.finalize());
+        let final_path = self.blob_path(&id);
+
+        if tokio::fs::metadata(&final_path).await.is_ok() {
+            let _ = tokio::fs::remove_file(&temp_path).await;
+        } else {
+            tokio::fs::rename(&temp_path, &final_path)
+                .await
+                .map_err(|e| {
+                    error!("Failed to finalize media upload: {:?}", e);
+                    "Failed to store media"
+                })?;
+        }
+
+        tokio::fs::write(self.meta_path(&id), content_type)
+            .await
+            .map_err(|e| {
+                error!("Failed to write media metadata: {:?}", e);
+                "Failed to store media"
+            })?;
+
+        Ok(id)
+    }
+
+    async fn get(&self, id: &str) -> Result<Option<StoredMedia>, String> {
+        if !is_valid_id(id) {
+            return Ok(None);
+        }
+
+        let path = self.blob_path(id);
+        let metadata = match tokio::fs::metadata(&path).await {
+            Ok(metadata) => metadata,
+            Err(_) => return Ok(None),
+        };
+
+        let content_type = tokio::fs::read_to_string(self.meta_path(id))
+            .await
+            .unwrap_or_else(|_| "application/octet-stream".to_string());
+
+        Ok(Some(StoredMedia {
+            content_type,
+            content_length: metadata.len(),
+            path,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_store() -> FsMediaStore {
+        FsMediaStore::new(std::env::temp_dir().join(format!("microblog-media-test-{}", Uuid::new_v4())))
+    }
+
+    #[test]
+    fn is_valid_id_rejects_path_traversal_and_malformed_ids() {
+        assert!(!is_valid_id("../../../etc/passwd"));
+        assert!(!is_valid_id("..%2f..%2fetc%2fpasswd"));
+        assert!(!is_valid_id(""));
+        assert!(!is_valid_id(&"a".repeat(63)));
+        assert!(!is_valid_id(&"a".repeat(65)));
+        assert!(!is_valid_id(&"A".repeat(64)));
+        assert!(!is_valid_id(&format!("{}/../../etc/passwd", "a".repeat(64))));
+    }
+
+    #[test]
+    fn is_valid_id_accepts_a_lowercase_sha256_digest() {
+        assert!(is_valid_id(&"a".repeat(64)));
+    }
+
+    #[tokio::test]
+    async fn put_then_get_round_trips_and_dedups_identical_content() {
+        let store = temp_store();
+
+        let id_a = store
+            .put("text/plain".to_string(), Body::from("hello"))
+            .await
+            .unwrap();
+        let id_b = store
+            .put("text/plain".to_string(), Body::from("hello"))
+            .await
+            .unwrap();
+        assert_eq!(id_a, id_b, "identical content should dedup onto one blob");
+
+        let stored = store.get(&id_a).await.unwrap().expect("blob should exist");
+        assert_eq!(stored.content_type, "text/plain");
+        assert_eq!(stored.content_length, 5);
+
+        tokio::fs::remove_dir_all(&store.base_dir).await.ok();
+    }
+
+    #[tokio::test]
+    async fn get_rejects_traversal_id_without_touching_the_filesystem() {
+        let store = temp_store();
+        assert!(store.get("../outside").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn get_returns_none_for_an_unknown_id() {
+        let store = temp_store();
+        assert!(store.get(&"0".repeat(64)).await.unwrap().is_none());
+    }
+}
@@ -0,0 +1,28 @@
+mod fs;
+
+pub use fs::FsMediaStore;
+
+use async_trait::async_trait;
+use axum::body::Body;
+
+/// A stored blob's metadata and on-disk path, from which `GET /media/{id}`
+/// streams the body.
+pub struct StoredMedia {
+    pub content_type: String,
+    pub content_length: u64,
+    pub path: std::path::PathBuf,
+}
+
+/// Content-addressed storage for post attachments.
+///
+/// Mirrors `Storage`: handlers hold a `dyn MediaStore` so the backend (here,
+/// the filesystem) can be swapped without touching `/media` routing.
+#[async_trait]
+pub trait MediaStore: Send + Sync {
+    /// Streams `body` to storage chunk-by-chunk, deriving the blob's id from
+    /// a hash of its contents, and returns that id.
+    async fn put(&self, content_type: String, body: Body) -> Result<String, String>;
+
+    /// Looks up a previously stored blob by id.
+    async fn get(&self, id: &str) -> Result<Option<StoredMedia>, String>;
+}
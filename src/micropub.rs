@@ -0,0 +1,126 @@
+use serde::Deserialize;
+
+/// A post's fields normalized out of either Micropub request shape.
+pub struct MicropubEntry {
+    pub title: String,
+    pub content: String,
+}
+
+/// Parses `application/x-www-form-urlencoded` Micropub requests
+/// (`h=entry&name=...&content=...`).
+pub fn parse_form(body: &[u8]) -> Result<MicropubEntry, String> {
+    let mut name = None;
+    let mut content = None;
+
+    for (key, value) in url::form_urlencoded::parse(body) {
+        match key.as_ref() {
+            "name" => name = Some(value.into_owned()),
+            "content" => content = Some(value.into_owned()),
+            _ => {}
+        }
+    }
+
+    let content = content.ok_or("content is required")?;
+    if content.is_empty() {
+        return Err("content is required".to_string());
+    }
+
+    Ok(MicropubEntry {
+        title: name.unwrap_or_default(),
+        content,
+    })
+}
+
+#[derive(Deserialize)]
+struct JsonEntry {
+    properties: JsonProperties,
+}
+
+#[derive(Deserialize, Default)]
+struct JsonProperties {
+    #[serde(default)]
+    name: Vec<String>,
+    #[serde(default)]
+    content: Vec<String>,
+}
+
+/// Parses the Micropub JSON shape (`{"type":["h-entry"],"properties":{...}}`).
+pub fn parse_json(body: &[u8]) -> Result<MicropubEntry, String> {
+    let entry: JsonEntry =
+        serde_json::from_slice(body).map_err(|e| format!("Invalid JSON: {}", e))?;
+
+    let content = entry
+        .properties
+        .content
+        .into_iter()
+        .next()
+        .ok_or("content is required")?;
+    if content.is_empty() {
+        return Err("content is required".to_string());
+    }
+    let title = entry.properties.name.into_iter().next().unwrap_or_default();
+
+    Ok(MicropubEntry { title, content })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_form_extracts_name_and_content() {
+        let entry = parse_form(b"h=entry&name=Title&content=Hello").unwrap();
+        assert_eq!(entry.title, "Title");
+        assert_eq!(entry.content, "Hello");
+    }
+
+    #[test]
+    fn parse_form_defaults_title_when_name_is_absent() {
+        let entry = parse_form(b"h=entry&content=Hello").unwrap();
+        assert_eq!(entry.title, "");
+        assert_eq!(entry.content, "Hello");
+    }
+
+    #[test]
+    fn parse_form_rejects_missing_content() {
+        assert_eq!(parse_form(b"h=entry&name=Title").unwrap_err(), "content is required");
+    }
+
+    #[test]
+    fn parse_form_rejects_empty_content() {
+        assert_eq!(parse_form(b"h=entry&content=").unwrap_err(), "content is required");
+    }
+
+    #[test]
+    fn parse_json_extracts_name_and_content() {
+        let body = br#"{"type":["h-entry"],"properties":{"name":["Title"],"content":["Hello"]}}"#;
+        let entry = parse_json(body).unwrap();
+        assert_eq!(entry.title, "Title");
+        assert_eq!(entry.content, "Hello");
+    }
+
+    #[test]
+    fn parse_json_defaults_title_when_name_is_absent() {
+        let body = br#"{"type":["h-entry"],"properties":{"content":["Hello"]}}"#;
+        let entry = parse_json(body).unwrap();
+        assert_eq!(entry.title, "");
+        assert_eq!(entry.content, "Hello");
+    }
+
+    #[test]
+    fn parse_json_rejects_missing_content() {
+        let body = br#"{"type":["h-entry"],"properties":{"name":["Title"]}}"#;
+        assert_eq!(parse_json(body).unwrap_err(), "content is required");
+    }
+
+    #[test]
+    fn parse_json_rejects_empty_content() {
+        let body = br#"{"type":["h-entry"],"properties":{"content":[""]}}"#;
+        assert_eq!(parse_json(body).unwrap_err(), "content is required");
+    }
+
+    #[test]
+    fn parse_json_rejects_invalid_json() {
+        assert!(parse_json(b"not json").is_err());
+    }
+}
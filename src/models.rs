@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Post {
+    pub id: Uuid,
+    /// Monotonic insertion order, used as the keyset pagination cursor.
+    pub seq: i64,
+    pub title: String,
+    pub content: String,
+}
+
+/// Keyset-pagination and search filters for `GET /posts`.
+#[derive(Clone, Debug)]
+pub struct PostFilter {
+    pub limit: u32,
+    pub before: Option<i64>,
+    pub query: Option<String>,
+}
+
+/// The paginated response envelope for `GET /posts`: a page of posts plus
+/// the cursor to request the next one, if any remain.
+#[derive(Serialize)]
+pub struct PostsPage {
+    pub posts: Vec<Post>,
+    pub next: Option<i64>,
+}
+
+/// A queued outbound webmention delivery, persisted so it survives restarts.
+///
+/// `endpoint` starts out `None`: discovery happens in the background worker,
+/// not on the request path that enqueued the job, so a slow or unresponsive
+/// link can't hang the `POST /posts`/`POST /micropub` response.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct WebmentionJob {
+    pub id: Uuid,
+    pub source: String,
+    pub target: String,
+    pub endpoint: Option<String>,
+    pub attempts: u32,
+}
+
+/// A verified inbound webmention, recording that `source` links to `target`.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Mention {
+    pub id: Uuid,
+    pub source: String,
+    pub target: String,
+}
+
+/// A bearer token's identity and granted scopes, as stored for a local
+/// `TokenVerifier` to look up.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TokenRecord {
+    pub me: String,
+    pub scopes: Vec<String>,
+}
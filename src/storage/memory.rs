@@ -0,0 +1,281 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicI64, Ordering};
+
+use async_trait::async_trait;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::models::{Mention, Post, PostFilter, PostsPage, TokenRecord, WebmentionJob};
+use crate::storage::Storage;
+
+/// An in-memory `Storage` backend.
+///
+/// Nothing is persisted across restarts. Unit tests use it directly or through
+/// a `Microblog` built with `memory://`; the e2e tests in `tests/e2e_tests.rs`
+/// still run against a live server on port 3000.
+#[derive(Default)]
+pub struct MemoryStore {
+    posts: RwLock<Vec<Post>>,
+    next_seq: AtomicI64,
+    webmention_queue: RwLock<Vec<WebmentionJob>>,
+    mentions: RwLock<Vec<Mention>>,
+    tokens: RwLock<HashMap<String, TokenRecord>>,
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        MemoryStore {
+            posts: RwLock::new(Vec::new()),
+            next_seq: AtomicI64::new(1),
+            webmention_queue: RwLock::new(Vec::new()),
+            mentions: RwLock::new(Vec::new()),
+            tokens: RwLock::new(HashMap::new()),
+        }
+    }
+}
+
+#[async_trait]
+impl Storage for MemoryStore {
+    async fn create_post(&self, title: String, content: String) -> Result<Post, String> {
+        let post = Post {
+            id: Uuid::new_v4(),
+            seq: self.next_seq.fetch_add(1, Ordering::SeqCst),
+            title,
+            content,
+        };
+
+        self.posts.write().await.push(post.clone());
+        Ok(post)
+    }
+
+    async fn get_posts(&self, filter: PostFilter) -> Result<PostsPage, String> {
+        let posts = self.posts.read().await;
+        let query = filter.query.map(|q| q.to_lowercase());
+
+        let mut matched: Vec<Post> = posts
+            .iter()
+            .filter(|post| filter.before.is_none_or(|before| post.seq < before))
+            .filter(|post| {
+                query.as_ref().is_none_or(|q| {
+                    post.title.to_lowercase().contains(q) || post.content.to_lowercase().contains(q)
+                })
+            })
+            .cloned()
+            .collect();
+        matched.sort_by(|a, b| b.seq.cmp(&a.seq));
+
+        let limit = filter.limit as usize;
+        let next = matched.get(limit).map(|post| post.seq);
+        matched.truncate(limit);
+
+        Ok(PostsPage {
+            posts: matched,
+            next,
+        })
+    }
+
+    async fn get_post(&self, id: Uuid) -> Result<Option<Post>, String> {
+        Ok(self.posts.read().await.iter().find(|post| post.id == id).cloned())
+    }
+
+    async fn enqueue_webmention(&self, source: String, target: String) -> Result<(), String> {
+        self.webmention_queue.write().await.push(WebmentionJob {
+            id: Uuid::new_v4(),
+            source,
+            target,
+            endpoint: None,
+            attempts: 0,
+        });
+        Ok(())
+    }
+
+    async fn pending_webmentions(&self) -> Result<Vec<WebmentionJob>, String> {
+        Ok(self.webmention_queue.read().await.clone())
+    }
+
+    async fn complete_webmention(&self, id: Uuid) -> Result<(), String> {
+        self.webmention_queue.write().await.retain(|job| job.id != id);
+        Ok(())
+    }
+
+    async fn reschedule_webmention(&self, id: Uuid) -> Result<(), String> {
+        if let Some(job) = self
+            .webmention_queue
+            .write()
+            .await
+            .iter_mut()
+            .find(|job| job.id == id)
+        {
+            job.attempts += 1;
+        }
+        Ok(())
+    }
+
+    async fn store_mention(&self, mention: Mention) -> Result<(), String> {
+        self.mentions.write().await.push(mention);
+        Ok(())
+    }
+
+    async fn lookup_token(&self, token: &str) -> Result<Option<TokenRecord>, String> {
+        Ok(self.tokens.read().await.get(token).cloned())
+    }
+
+    async fn insert_token(&self, token: String, record: TokenRecord) -> Result<(), String> {
+        self.tokens.write().await.insert(token, record);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::Storage;
+
+    #[tokio::test]
+    async fn create_post_assigns_increasing_seq() {
+        let store = MemoryStore::new();
+
+        let first = store.create_post("a".to_string(), "a".to_string()).await.unwrap();
+        let second = store.create_post("b".to_string(), "b".to_string()).await.unwrap();
+
+        assert!(second.seq > first.seq);
+    }
+
+    #[tokio::test]
+    async fn get_posts_orders_newest_first() {
+        let store = MemoryStore::new();
+        store.create_post("first".to_string(), "1".to_string()).await.unwrap();
+        store.create_post("second".to_string(), "2".to_string()).await.unwrap();
+
+        let page = store
+            .get_posts(PostFilter {
+                limit: 10,
+                before: None,
+                query: None,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(page.posts.len(), 2);
+        assert_eq!(page.posts[0].title, "second");
+        assert_eq!(page.posts[1].title, "first");
+        assert!(page.next.is_none());
+    }
+
+    #[tokio::test]
+    async fn get_posts_paginates_with_a_next_cursor() {
+        let store = MemoryStore::new();
+        for i in 0..3 {
+            store
+                .create_post(format!("post {}", i), "content".to_string())
+                .await
+                .unwrap();
+        }
+
+        let first_page = store
+            .get_posts(PostFilter {
+                limit: 2,
+                before: None,
+                query: None,
+            })
+            .await
+            .unwrap();
+        assert_eq!(first_page.posts.len(), 2);
+        let next = first_page.next.expect("a third post should remain");
+
+        let second_page = store
+            .get_posts(PostFilter {
+                limit: 2,
+                before: Some(next),
+                query: None,
+            })
+            .await
+            .unwrap();
+        assert_eq!(second_page.posts.len(), 1);
+        assert!(second_page.next.is_none());
+    }
+
+    #[tokio::test]
+    async fn get_posts_filters_by_query_case_insensitively_across_title_and_content() {
+        let store = MemoryStore::new();
+        store
+            .create_post("Hello World".to_string(), "nothing relevant".to_string())
+            .await
+            .unwrap();
+        store
+            .create_post("Unrelated".to_string(), "mentions HELLO in passing".to_string())
+            .await
+            .unwrap();
+        store
+            .create_post("Nope".to_string(), "no match here".to_string())
+            .await
+            .unwrap();
+
+        let page = store
+            .get_posts(PostFilter {
+                limit: 10,
+                before: None,
+                query: Some("hello".to_string()),
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(page.posts.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn get_post_finds_by_id_and_misses_unknown_ids() {
+        let store = MemoryStore::new();
+        let post = store.create_post("a".to_string(), "a".to_string()).await.unwrap();
+
+        assert_eq!(store.get_post(post.id).await.unwrap().unwrap().id, post.id);
+        assert!(store.get_post(Uuid::new_v4()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn webmention_queue_lifecycle() {
+        let store = MemoryStore::new();
+        store
+            .enqueue_webmention(
+                "https://a.example/".to_string(),
+                "https://b.example/".to_string(),
+            )
+            .await
+            .unwrap();
+
+        let pending = store.pending_webmentions().await.unwrap();
+        assert_eq!(pending.len(), 1);
+        let job = &pending[0];
+        assert_eq!(job.attempts, 0);
+        assert!(
+            job.endpoint.is_none(),
+            "endpoint is discovered later by the background worker, not at enqueue time"
+        );
+
+        store.reschedule_webmention(job.id).await.unwrap();
+        let pending = store.pending_webmentions().await.unwrap();
+        assert_eq!(pending[0].attempts, 1);
+
+        store.complete_webmention(job.id).await.unwrap();
+        assert!(store.pending_webmentions().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn tokens_round_trip() {
+        let store = MemoryStore::new();
+        store
+            .insert_token(
+                "tok".to_string(),
+                TokenRecord {
+                    me: "https://example.com/".to_string(),
+                    scopes: vec!["create".to_string()],
+                },
+            )
+            .await
+            .unwrap();
+
+        let record = store.lookup_token("tok").await.unwrap().expect("token should exist");
+        assert_eq!(record.me, "https://example.com/");
+        assert!(store.lookup_token("missing").await.unwrap().is_none());
+    }
+}
@@ -0,0 +1,101 @@
+mod memory;
+mod postgres;
+mod sqlite;
+
+pub use memory::MemoryStore;
+pub use postgres::PostgresStore;
+pub use sqlite::SqliteStore;
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::models::{Mention, Post, PostFilter, PostsPage, TokenRecord, WebmentionJob};
+
+/// Default and maximum page size for `GET /posts`.
+pub const DEFAULT_POST_LIMIT: u32 = 20;
+pub const MAX_POST_LIMIT: u32 = 100;
+
+/// Escapes `%`, `_`, and the escape character itself, then wraps `term` in
+/// `%...%`, so a `LIKE`/`ILIKE ... ESCAPE '\'` pattern built from it matches
+/// `term` as a literal substring instead of treating `%`/`_` as wildcards.
+pub(crate) fn like_pattern(term: &str) -> String {
+    let escaped = term
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("%{}%", escaped)
+}
+
+/// Backend-agnostic persistence for posts.
+///
+/// `Microblog` holds a `dyn Storage` so the HTTP handlers never need to know
+/// which backend is actually serving a request.
+#[async_trait]
+pub trait Storage: Send + Sync {
+    async fn create_post(&self, title: String, content: String) -> Result<Post, String>;
+
+    /// Returns a page of posts matching `filter`, newest first.
+    async fn get_posts(&self, filter: PostFilter) -> Result<PostsPage, String>;
+
+    /// Looks up a single post by id, for `GET /posts/{id}` -- notably the
+    /// URL a receiving site's webmention endpoint fetches back to verify our
+    /// outbound mention's `source` really links to its `target`.
+    async fn get_post(&self, id: Uuid) -> Result<Option<Post>, String>;
+
+    /// Queues an outbound link for the background worker to discover a
+    /// webmention endpoint for (if any) and deliver to.
+    async fn enqueue_webmention(&self, source: String, target: String) -> Result<(), String>;
+
+    /// Returns webmentions still awaiting (re)delivery.
+    async fn pending_webmentions(&self) -> Result<Vec<WebmentionJob>, String>;
+
+    /// Drops a job once it has been delivered successfully.
+    async fn complete_webmention(&self, id: Uuid) -> Result<(), String>;
+
+    /// Bumps a job's attempt count after a failed delivery, for backoff.
+    async fn reschedule_webmention(&self, id: Uuid) -> Result<(), String>;
+
+    /// Records a verified inbound webmention.
+    async fn store_mention(&self, mention: Mention) -> Result<(), String>;
+
+    /// Looks up a bearer token for a `LocalTokenVerifier` to verify.
+    async fn lookup_token(&self, token: &str) -> Result<Option<TokenRecord>, String>;
+
+    /// Registers a bearer token. There's no token-issuing endpoint yet, so
+    /// operators (and e2e tests) provision tokens directly through this.
+    async fn insert_token(&self, token: String, record: TokenRecord) -> Result<(), String>;
+}
+
+/// Builds the storage backend indicated by `database_url`'s scheme, running
+/// that backend's migrations (if any) before handing it back.
+///
+/// Supported schemes: `sqlite://`, `postgres://`/`postgresql://`, and
+/// `memory://` (handy for tests, since it needs no live database and no
+/// migrations to run).
+pub async fn from_database_url(
+    database_url: &str,
+) -> Result<std::sync::Arc<dyn Storage>, Box<dyn std::error::Error + Send + Sync>> {
+    if database_url.starts_with("postgres://") || database_url.starts_with("postgresql://") {
+        Ok(std::sync::Arc::new(PostgresStore::new(database_url).await?))
+    } else if database_url.starts_with("memory://") {
+        Ok(std::sync::Arc::new(MemoryStore::new()))
+    } else {
+        Ok(std::sync::Arc::new(SqliteStore::new(database_url).await?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn like_pattern_wraps_term_for_substring_match() {
+        assert_eq!(like_pattern("hello"), "%hello%");
+    }
+
+    #[test]
+    fn like_pattern_escapes_wildcards_so_they_match_literally() {
+        assert_eq!(like_pattern("50%_off"), "%50\\%\\_off%");
+        assert_eq!(like_pattern(r"back\slash"), r"%back\\slash%");
+    }
+}
@@ -0,0 +1,220 @@
+use async_trait::async_trait;
+use log::error;
+use sqlx::{PgPool, Pool, Postgres, Row};
+use uuid::Uuid;
+
+use crate::models::{Mention, Post, PostFilter, PostsPage, TokenRecord, WebmentionJob};
+use crate::storage::Storage;
+
+/// `Storage` backed by Postgres, for deployments that outgrow SQLite.
+///
+/// Unlike sqlite, Postgres has no implicit rowid to lean on for the
+/// pagination cursor, so `posts` needs an explicit
+/// `seq BIGSERIAL NOT NULL` column alongside the existing ones.
+pub struct PostgresStore {
+    pool: Pool<Postgres>,
+}
+
+impl PostgresStore {
+    pub async fn new(
+        database_url: &str,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let pool = PgPool::connect_lazy(database_url)?;
+        sqlx::migrate!("./migrations/postgres").run(&pool).await?;
+        Ok(PostgresStore { pool })
+    }
+}
+
+#[async_trait]
+impl Storage for PostgresStore {
+    async fn create_post(&self, title: String, content: String) -> Result<Post, String> {
+        let id = Uuid::new_v4();
+
+        let row = sqlx::query!(
+            "INSERT INTO posts (id, title, content) VALUES ($1, $2, $3) RETURNING seq",
+            id,
+            title,
+            content
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database insert error: {:?}", e);
+            "Failed to insert post into database"
+        })?;
+
+        Ok(Post {
+            id,
+            seq: row.seq,
+            title,
+            content,
+        })
+    }
+
+    async fn get_posts(&self, filter: PostFilter) -> Result<PostsPage, String> {
+        // Fetch one extra row so we can tell whether another page follows.
+        let query_limit = i64::from(filter.limit) + 1;
+        let pattern = filter.query.as_ref().map(|q| crate::storage::like_pattern(q));
+
+        let rows = sqlx::query(
+            "SELECT seq, id, title, content FROM posts \
+             WHERE ($1::bigint IS NULL OR seq < $1) \
+               AND ($2::text IS NULL OR title ILIKE $2 ESCAPE '\\' OR content ILIKE $2 ESCAPE '\\') \
+             ORDER BY seq DESC LIMIT $3",
+        )
+        .bind(filter.before)
+        .bind(&pattern)
+        .bind(query_limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database fetch error: {:?}", e);
+            "Failed to fetch posts from database"
+        })?;
+
+        let mut posts: Vec<Post> = rows
+            .into_iter()
+            .map(|row| Post {
+                id: row.get("id"),
+                seq: row.get("seq"),
+                title: row.get("title"),
+                content: row.get("content"),
+            })
+            .collect();
+
+        let limit = filter.limit as usize;
+        let next = posts.get(limit).map(|post| post.seq);
+        posts.truncate(limit);
+
+        Ok(PostsPage { posts, next })
+    }
+
+    async fn get_post(&self, id: Uuid) -> Result<Option<Post>, String> {
+        let row = sqlx::query("SELECT seq, id, title, content FROM posts WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| {
+                error!("Database fetch error: {:?}", e);
+                "Failed to fetch post from database"
+            })?;
+
+        Ok(row.map(|row| Post {
+            id: row.get("id"),
+            seq: row.get("seq"),
+            title: row.get("title"),
+            content: row.get("content"),
+        }))
+    }
+
+    async fn enqueue_webmention(&self, source: String, target: String) -> Result<(), String> {
+        let id = Uuid::new_v4();
+        sqlx::query!(
+            "INSERT INTO webmention_queue (id, source, target, endpoint, attempts) VALUES ($1, $2, $3, NULL, 0)",
+            id,
+            source,
+            target
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Failed to enqueue webmention: {:?}", e);
+            "Failed to enqueue webmention"
+        })?;
+        Ok(())
+    }
+
+    async fn pending_webmentions(&self) -> Result<Vec<WebmentionJob>, String> {
+        let rows = sqlx::query!("SELECT id, source, target, endpoint, attempts FROM webmention_queue")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| {
+                error!("Failed to fetch pending webmentions: {:?}", e);
+                "Failed to fetch pending webmentions"
+            })?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| WebmentionJob {
+                id: row.id,
+                source: row.source,
+                target: row.target,
+                endpoint: row.endpoint,
+                attempts: row.attempts as u32,
+            })
+            .collect())
+    }
+
+    async fn complete_webmention(&self, id: Uuid) -> Result<(), String> {
+        sqlx::query!("DELETE FROM webmention_queue WHERE id = $1", id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                error!("Failed to complete webmention: {:?}", e);
+                "Failed to complete webmention"
+            })?;
+        Ok(())
+    }
+
+    async fn reschedule_webmention(&self, id: Uuid) -> Result<(), String> {
+        sqlx::query!(
+            "UPDATE webmention_queue SET attempts = attempts + 1 WHERE id = $1",
+            id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Failed to reschedule webmention: {:?}", e);
+            "Failed to reschedule webmention"
+        })?;
+        Ok(())
+    }
+
+    async fn store_mention(&self, mention: Mention) -> Result<(), String> {
+        sqlx::query!(
+            "INSERT INTO mentions (id, source, target) VALUES ($1, $2, $3)",
+            mention.id,
+            mention.source,
+            mention.target
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Failed to store mention: {:?}", e);
+            "Failed to store mention"
+        })?;
+        Ok(())
+    }
+
+    async fn lookup_token(&self, token: &str) -> Result<Option<TokenRecord>, String> {
+        let row = sqlx::query!("SELECT me, scopes FROM tokens WHERE token = $1", token)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| {
+                error!("Failed to look up token: {:?}", e);
+                "Failed to look up token"
+            })?;
+
+        Ok(row.map(|row| TokenRecord {
+            me: row.me,
+            scopes: row.scopes.split_whitespace().map(str::to_string).collect(),
+        }))
+    }
+
+    async fn insert_token(&self, token: String, record: TokenRecord) -> Result<(), String> {
+        let scopes = record.scopes.join(" ");
+        sqlx::query!(
+            "INSERT INTO tokens (token, me, scopes) VALUES ($1, $2, $3)",
+            token,
+            record.me,
+            scopes
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Failed to insert token: {:?}", e);
+            "Failed to insert token"
+        })?;
+        Ok(())
+    }
+}
@@ -0,0 +1,226 @@
+use async_trait::async_trait;
+use log::error;
+use sqlx::{Pool, Row, Sqlite, SqlitePool};
+use uuid::Uuid;
+
+use crate::models::{Mention, Post, PostFilter, PostsPage, TokenRecord, WebmentionJob};
+use crate::storage::Storage;
+
+/// The original `sqlx`-backed store, now living behind the `Storage` trait
+/// instead of being baked directly into `Microblog`.
+pub struct SqliteStore {
+    pool: Pool<Sqlite>,
+}
+
+impl SqliteStore {
+    pub async fn new(
+        database_url: &str,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let pool = SqlitePool::connect_lazy(database_url)?;
+        sqlx::migrate!("./migrations/sqlite").run(&pool).await?;
+        Ok(SqliteStore { pool })
+    }
+}
+
+#[async_trait]
+impl Storage for SqliteStore {
+    async fn create_post(&self, title: String, content: String) -> Result<Post, String> {
+        let id = Uuid::new_v4();
+        let id_string = id.to_string();
+
+        // sqlite already gives every row a monotonic `rowid`, so it doubles
+        // as the pagination cursor without an explicit schema migration.
+        let result = sqlx::query!(
+            "INSERT INTO posts (id, title, content) VALUES (?, ?, ?)",
+            id_string,
+            title,
+            content
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database insert error: {:?}", e);
+            "Failed to insert post into database"
+        })?;
+
+        Ok(Post {
+            id,
+            seq: result.last_insert_rowid(),
+            title,
+            content,
+        })
+    }
+
+    async fn get_posts(&self, filter: PostFilter) -> Result<PostsPage, String> {
+        // Fetch one extra row so we can tell whether another page follows.
+        let query_limit = i64::from(filter.limit) + 1;
+        let pattern = filter.query.as_ref().map(|q| crate::storage::like_pattern(q));
+
+        let rows = sqlx::query(
+            "SELECT rowid AS seq, id, title, content FROM posts \
+             WHERE (?1 IS NULL OR rowid < ?1) \
+               AND (?2 IS NULL OR title LIKE ?2 ESCAPE '\\' OR content LIKE ?2 ESCAPE '\\') \
+             ORDER BY rowid DESC LIMIT ?3",
+        )
+        .bind(filter.before)
+        .bind(&pattern)
+        .bind(query_limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database fetch error: {:?}", e);
+            "Failed to fetch posts from database"
+        })?;
+
+        let mut posts: Vec<Post> = rows
+            .into_iter()
+            .map(|row| Post {
+                id: Uuid::parse_str(&row.get::<String, _>("id")).expect("Invalid UUID in DB"),
+                seq: row.get("seq"),
+                title: row.get("title"),
+                content: row.get("content"),
+            })
+            .collect();
+
+        let limit = filter.limit as usize;
+        let next = posts.get(limit).map(|post| post.seq);
+        posts.truncate(limit);
+
+        Ok(PostsPage { posts, next })
+    }
+
+    async fn get_post(&self, id: Uuid) -> Result<Option<Post>, String> {
+        let id_string = id.to_string();
+        let row = sqlx::query(
+            "SELECT rowid AS seq, id, title, content FROM posts WHERE id = ?1",
+        )
+        .bind(&id_string)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database fetch error: {:?}", e);
+            "Failed to fetch post from database"
+        })?;
+
+        Ok(row.map(|row| Post {
+            id,
+            seq: row.get("seq"),
+            title: row.get("title"),
+            content: row.get("content"),
+        }))
+    }
+
+    async fn enqueue_webmention(&self, source: String, target: String) -> Result<(), String> {
+        let id_string = Uuid::new_v4().to_string();
+        sqlx::query!(
+            "INSERT INTO webmention_queue (id, source, target, endpoint, attempts) VALUES (?, ?, ?, NULL, 0)",
+            id_string,
+            source,
+            target
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Failed to enqueue webmention: {:?}", e);
+            "Failed to enqueue webmention"
+        })?;
+        Ok(())
+    }
+
+    async fn pending_webmentions(&self) -> Result<Vec<WebmentionJob>, String> {
+        let rows = sqlx::query!("SELECT id, source, target, endpoint, attempts FROM webmention_queue")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| {
+                error!("Failed to fetch pending webmentions: {:?}", e);
+                "Failed to fetch pending webmentions"
+            })?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| WebmentionJob {
+                id: Uuid::parse_str(row.id.as_deref().unwrap_or("")).expect("Invalid UUID in DB"),
+                source: row.source,
+                target: row.target,
+                endpoint: row.endpoint,
+                attempts: row.attempts as u32,
+            })
+            .collect())
+    }
+
+    async fn complete_webmention(&self, id: Uuid) -> Result<(), String> {
+        let id_string = id.to_string();
+        sqlx::query!("DELETE FROM webmention_queue WHERE id = ?", id_string)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                error!("Failed to complete webmention: {:?}", e);
+                "Failed to complete webmention"
+            })?;
+        Ok(())
+    }
+
+    async fn reschedule_webmention(&self, id: Uuid) -> Result<(), String> {
+        let id_string = id.to_string();
+        sqlx::query!(
+            "UPDATE webmention_queue SET attempts = attempts + 1 WHERE id = ?",
+            id_string
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Failed to reschedule webmention: {:?}", e);
+            "Failed to reschedule webmention"
+        })?;
+        Ok(())
+    }
+
+    async fn store_mention(&self, mention: Mention) -> Result<(), String> {
+        let id_string = mention.id.to_string();
+        sqlx::query!(
+            "INSERT INTO mentions (id, source, target) VALUES (?, ?, ?)",
+            id_string,
+            mention.source,
+            mention.target
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Failed to store mention: {:?}", e);
+            "Failed to store mention"
+        })?;
+        Ok(())
+    }
+
+    async fn lookup_token(&self, token: &str) -> Result<Option<TokenRecord>, String> {
+        let row = sqlx::query!("SELECT me, scopes FROM tokens WHERE token = ?", token)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| {
+                error!("Failed to look up token: {:?}", e);
+                "Failed to look up token"
+            })?;
+
+        Ok(row.map(|row| TokenRecord {
+            me: row.me,
+            scopes: row.scopes.split_whitespace().map(str::to_string).collect(),
+        }))
+    }
+
+    async fn insert_token(&self, token: String, record: TokenRecord) -> Result<(), String> {
+        let scopes = record.scopes.join(" ");
+        sqlx::query!(
+            "INSERT INTO tokens (token, me, scopes) VALUES (?, ?, ?)",
+            token,
+            record.me,
+            scopes
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Failed to insert token: {:?}", e);
+            "Failed to insert token"
+        })?;
+        Ok(())
+    }
+}
@@ -0,0 +1,44 @@
+use std::fs::File;
+use std::io::BufReader;
+use std::sync::Arc;
+use std::time::Duration;
+
+use rustls_pemfile::{certs, pkcs8_private_keys};
+use tokio_rustls::rustls::pki_types::CertificateDer;
+use tokio_rustls::rustls::ServerConfig;
+use tokio_rustls::TlsAcceptor;
+
+/// Loads a PEM certificate chain and private key into a `TlsAcceptor` for
+/// terminating HTTPS connections. Returns `Err` if either file is missing or
+/// malformed.
+pub fn load_acceptor(
+    cert_path: &str,
+    key_path: &str,
+) -> Result<TlsAcceptor, Box<dyn std::error::Error + Send + Sync>> {
+    let cert_chain: Vec<CertificateDer<'static>> =
+        certs(&mut BufReader::new(File::open(cert_path)?)).collect::<Result<_, _>>()?;
+
+    let key = pkcs8_private_keys(&mut BufReader::new(File::open(key_path)?))
+        .next()
+        .ok_or("No private key found in key file")??
+        .into();
+
+    let config = ServerConfig::builder()
+        .with_no_client_auth()
+        .with_single_cert(cert_chain, key)?;
+
+    Ok(TlsAcceptor::from(Arc::new(config)))
+}
+
+/// Builds an HTTP client for outbound requests (webmention discovery and
+/// delivery, remote token verification) that validates peers against the OS
+/// trust store. A bounded timeout keeps a slow or unresponsive peer from
+/// hanging whatever called in -- including a request still on the critical
+/// path (inbound webmention source verification).
+pub fn http_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .tls_built_in_native_certs(true)
+        .timeout(Duration::from_secs(10))
+        .build()
+        .expect("Failed to build HTTP client")
+}
@@ -0,0 +1,123 @@
+use log::warn;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use url::Url;
+
+/// Matches `<link rel="webmention" href="...">` and `<a rel="webmention" href="...">`,
+/// independent of attribute order.
+static WEBMENTION_TAG: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?is)<(?:link|a)\s+[^>]*rel=["']webmention["'][^>]*href=["']([^"']+)["']|<(?:link|a)\s+[^>]*href=["']([^"']+)["'][^>]*rel=["']webmention["']"#)
+        .expect("invalid webmention regex")
+});
+
+/// Discovers the webmention endpoint for `target`, per the spec: prefer an
+/// HTTP `Link: <url>; rel="webmention"` header, falling back to a `<link>`
+/// or `<a rel="webmention">` tag in the HTML body. Relative endpoints are
+/// resolved against `target`.
+pub async fn discover_endpoint(client: &reqwest::Client, target: &str) -> Option<String> {
+    let response = match client.get(target).send().await {
+        Ok(response) => response,
+        Err(e) => {
+            warn!("Webmention discovery request to {} failed: {:?}", target, e);
+            return None;
+        }
+    };
+
+    if let Some(link_header) = response.headers().get(reqwest::header::LINK) {
+        if let Ok(value) = link_header.to_str() {
+            if let Some(endpoint) = parse_link_header(value) {
+                return resolve(target, &endpoint);
+            }
+        }
+    }
+
+    let body = match response.text().await {
+        Ok(body) => body,
+        Err(e) => {
+            warn!("Failed to read discovery body for {}: {:?}", target, e);
+            return None;
+        }
+    };
+
+    let captures = WEBMENTION_TAG.captures(&body)?;
+    let endpoint = captures
+        .get(1)
+        .or_else(|| captures.get(2))
+        .map(|m| m.as_str())?;
+    resolve(target, endpoint)
+}
+
+/// Pulls the URL out of a `Link` header that carries `rel="webmention"`.
+fn parse_link_header(value: &str) -> Option<String> {
+    value.split(',').find_map(|part| {
+        if !part.contains("rel=\"webmention\"") && !part.contains("rel=webmention") {
+            return None;
+        }
+        let start = part.find('<')? + 1;
+        let end = part.find('>')?;
+        Some(part[start..end].to_string())
+    })
+}
+
+fn resolve(base: &str, endpoint: &str) -> Option<String> {
+    match Url::parse(endpoint) {
+        Ok(url) => Some(url.to_string()),
+        Err(_) => Url::parse(base)
+            .ok()?
+            .join(endpoint)
+            .ok()
+            .map(|url| url.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_link_header_with_rel_webmention() {
+        let header = r#"<https://example.com/webmention>; rel="webmention", <https://example.com/other>; rel="alternate""#;
+        assert_eq!(
+            parse_link_header(header),
+            Some("https://example.com/webmention".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_link_header_ignores_unrelated_rels() {
+        let header = r#"<https://example.com/other>; rel="alternate""#;
+        assert_eq!(parse_link_header(header), None);
+    }
+
+    #[test]
+    fn webmention_tag_matches_link_tag_with_rel_before_href() {
+        let body = r#"<html><head><link rel="webmention" href="https://example.com/wm"></head></html>"#;
+        let captures = WEBMENTION_TAG.captures(body).expect("should match");
+        let endpoint = captures.get(1).or_else(|| captures.get(2)).unwrap().as_str();
+        assert_eq!(endpoint, "https://example.com/wm");
+    }
+
+    #[test]
+    fn webmention_tag_matches_anchor_tag_with_href_before_rel() {
+        let body = r#"<a href="/wm" rel="webmention">webmention</a>"#;
+        let captures = WEBMENTION_TAG.captures(body).expect("should match");
+        let endpoint = captures.get(1).or_else(|| captures.get(2)).unwrap().as_str();
+        assert_eq!(endpoint, "/wm");
+    }
+
+    #[test]
+    fn resolves_relative_endpoint_against_target() {
+        assert_eq!(
+            resolve("https://example.com/posts/1", "/webmention"),
+            Some("https://example.com/webmention".to_string())
+        );
+    }
+
+    #[test]
+    fn resolves_absolute_endpoint_unchanged() {
+        assert_eq!(
+            resolve("https://example.com/posts/1", "https://other.example/wm"),
+            Some("https://other.example/wm".to_string())
+        );
+    }
+}
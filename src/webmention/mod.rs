@@ -0,0 +1,37 @@
+mod discovery;
+mod worker;
+
+pub use discovery::discover_endpoint;
+pub use worker::spawn_worker;
+
+use linkify::{LinkFinder, LinkKind};
+
+/// Pulls every `http(s)://` link out of a post's content so each can be
+/// checked for a webmention endpoint.
+pub fn extract_links(content: &str) -> Vec<String> {
+    let finder = LinkFinder::new();
+    finder
+        .links(content)
+        .filter(|link| *link.kind() == LinkKind::Url)
+        .map(|link| link.as_str().to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_http_links_and_ignores_surrounding_text() {
+        let content = "check out http://example.com/post and also https://example.org/, thanks";
+        assert_eq!(
+            extract_links(content),
+            vec!["http://example.com/post", "https://example.org/"]
+        );
+    }
+
+    #[test]
+    fn returns_empty_for_content_with_no_links() {
+        assert!(extract_links("just some text, no links here").is_empty());
+    }
+}
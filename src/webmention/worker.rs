@@ -0,0 +1,82 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use log::{error, info};
+use tokio::task::JoinHandle;
+
+use crate::storage::Storage;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+const MAX_ATTEMPTS: u32 = 8;
+
+/// Spawns the background task that drains the webmention delivery queue.
+///
+/// Jobs are persisted in `storage`, so a crash or restart just resumes
+/// delivery rather than losing pending mentions.
+pub fn spawn_worker(storage: Arc<dyn Storage>) -> JoinHandle<()> {
+    tokio::task::spawn(async move {
+        let client = crate::tls::http_client();
+        loop {
+            match storage.pending_webmentions().await {
+                Ok(jobs) => {
+                    // Spawn each delivery as its own detached task instead of
+                    // awaiting the batch, so one job's backoff sleep can't
+                    // stall the next poll of the queue behind it.
+                    for job in jobs {
+                        let client = client.clone();
+                        let storage = Arc::clone(&storage);
+                        tokio::task::spawn(async move {
+                            deliver(&client, &*storage, job).await;
+                        });
+                    }
+                }
+                Err(e) => error!("Failed to load pending webmentions: {}", e),
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    })
+}
+
+async fn deliver(client: &reqwest::Client, storage: &dyn Storage, job: crate::models::WebmentionJob) {
+    if job.attempts > 0 {
+        let backoff = Duration::from_secs(2u64.saturating_pow(job.attempts.min(10)));
+        tokio::time::sleep(backoff).await;
+    }
+
+    // Discovery happens here, off the request path that enqueued this job,
+    // rather than in queue_outbound_webmentions.
+    let endpoint = match job.endpoint {
+        Some(endpoint) => endpoint,
+        None => match super::discover_endpoint(client, &job.target).await {
+            Some(endpoint) => endpoint,
+            None => {
+                // No webmention endpoint advertised; nothing to deliver.
+                let _ = storage.complete_webmention(job.id).await;
+                return;
+            }
+        },
+    };
+
+    let body = [("source", job.source.as_str()), ("target", job.target.as_str())];
+    let result = client.post(&endpoint).form(&body).send().await;
+
+    match result {
+        Ok(response) if response.status().is_success() => {
+            info!("Delivered webmention {} -> {}", job.source, job.target);
+            if let Err(e) = storage.complete_webmention(job.id).await {
+                error!("Failed to mark webmention {} complete: {}", job.id, e);
+            }
+        }
+        _ => {
+            if job.attempts + 1 >= MAX_ATTEMPTS {
+                error!(
+                    "Giving up on webmention {} -> {} after {} attempts",
+                    job.source, job.target, job.attempts + 1
+                );
+                let _ = storage.complete_webmention(job.id).await;
+            } else if let Err(e) = storage.reschedule_webmention(job.id).await {
+                error!("Failed to reschedule webmention {}: {}", job.id, e);
+            }
+        }
+    }
+}
@@ -3,6 +3,12 @@ mod tests {
     use reqwest::StatusCode;
     use serde_json::json;
 
+    // The server provisions this token on startup when `DEV_AUTH_TOKEN` is
+    // set; these tests expect to run against a server started that way.
+    fn dev_auth_token() -> String {
+        std::env::var("DEV_AUTH_TOKEN").unwrap_or_else(|_| "dev-token".to_string())
+    }
+
     #[tokio::test]
     async fn test_create_post() {
         let url = "http://127.0.0.1:3000/posts";
@@ -10,6 +16,7 @@ mod tests {
 
         let client = reqwest::Client::new();
         let response = client.post(url)
+            .bearer_auth(dev_auth_token())
             .json(&body)
             .send()
             .await
@@ -35,9 +42,9 @@ mod tests {
         assert_eq!(response.status(), StatusCode::OK);
 
         let json_response: serde_json::Value = response.json().await.expect("Failed to parse response");
-        assert!(json_response.is_array(), "Response should be a JSON array");
+        assert!(json_response.is_object(), "Response should be a JSON object");
 
-        let posts_array = json_response.as_array().expect("Failed to convert JSON to array");
+        let posts_array = json_response["posts"].as_array().expect("Expected a \"posts\" array");
         assert!(!posts_array.is_empty(), "Post array should not be empty");
         assert!(posts_array.len() > 1, "Expected 1 post in the database");
 
@@ -46,4 +53,33 @@ mod tests {
             assert!(first_post["content"].is_string(), "Content should be a string");
         }
     }
+
+    #[tokio::test]
+    async fn test_get_posts_pagination() {
+        let client = reqwest::Client::new();
+
+        for i in 0..3 {
+            let body = json!({ "title": format!("Page post {}", i), "content": "paginated" });
+            client
+                .post("http://127.0.0.1:3000/posts")
+                .bearer_auth(dev_auth_token())
+                .json(&body)
+                .send()
+                .await
+                .expect("Failed to send request");
+        }
+
+        let response = client
+            .get("http://127.0.0.1:3000/posts?limit=1")
+            .send()
+            .await
+            .expect("Failed to send request");
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let json_response: serde_json::Value = response.json().await.expect("Failed to parse response");
+        let posts_array = json_response["posts"].as_array().expect("Expected a \"posts\" array");
+        assert_eq!(posts_array.len(), 1, "Expected exactly one post for limit=1");
+        assert!(json_response["next"].is_number(), "Expected a next cursor when more posts remain");
+    }
 }
\ No newline at end of file